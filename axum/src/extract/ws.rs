@@ -73,7 +73,7 @@
 //! [`StreamExt::split`]:
 //!
 //! ```rust,no_run
-//! use axum::{Error, extract::ws::{WebSocket, Message}};
+//! use axum::extract::ws::{WebSocket, Message};
 //! use futures::{sink::SinkExt, stream::{StreamExt, SplitSink, SplitStream}};
 //!
 //! async fn handle_socket(mut socket: WebSocket) {
@@ -99,7 +99,6 @@ use super::FromRequestParts;
 use crate::{
     body::{self, Bytes},
     response::Response,
-    Error,
 };
 use async_trait::async_trait;
 use futures_util::{
@@ -109,16 +108,20 @@ use futures_util::{
 use http::{
     header::{self, HeaderMap, HeaderName, HeaderValue},
     request::Parts,
-    Method, StatusCode,
+    response::Builder as ResponseBuilder,
+    Method, StatusCode, Version,
 };
 use hyper::upgrade::{OnUpgrade, Upgraded};
 use sha1::{Digest, Sha1};
 use std::{
     borrow::Cow,
+    fmt,
     future::Future,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
+use tokio::time::{Instant as TokioInstant, Interval};
 use tokio_tungstenite::{
     tungstenite::{
         self as ts,
@@ -143,8 +146,26 @@ pub struct WebSocketUpgrade {
     sec_websocket_key: HeaderValue,
     on_upgrade: OnUpgrade,
     sec_websocket_protocol: Option<HeaderValue>,
+    /// Additional headers to merge into the handshake response (`101 Switching Protocols`, or
+    /// `200 OK` over HTTP/2).
+    headers: HeaderMap,
+    keepalive_interval: Option<Duration>,
+    keepalive_timeout: Option<Duration>,
+    /// Whether this upgrade was negotiated over HTTP/2 Extended CONNECT (RFC 8441) rather than
+    /// an HTTP/1.1 `Upgrade`.
+    http2: bool,
 }
 
+/// Headers that axum computes itself as part of the handshake. Values set via
+/// [`WebSocketUpgrade::header`] or [`WebSocketUpgrade::headers`] for these names are ignored,
+/// since overriding them would produce a broken handshake response.
+const RESERVED_RESPONSE_HEADERS: [HeaderName; 4] = [
+    header::CONNECTION,
+    header::UPGRADE,
+    header::SEC_WEBSOCKET_ACCEPT,
+    header::SEC_WEBSOCKET_PROTOCOL,
+];
+
 impl WebSocketUpgrade {
     /// Set the size of the internal message send queue.
     pub fn max_send_queue(mut self, max: usize) -> Self {
@@ -152,18 +173,50 @@ impl WebSocketUpgrade {
         self
     }
 
-    /// Set the maximum message size (defaults to 64 megabytes)
+    /// Set the maximum message size (defaults to 64 megabytes).
+    ///
+    /// Caps how much memory a single reassembled message may consume. A peer that exceeds this
+    /// surfaces as [`WebSocketError::Capacity`] on the [`WebSocket`] stream, so the handler can
+    /// close the connection with an appropriate status code instead of the process running out
+    /// of memory.
     pub fn max_message_size(mut self, max: usize) -> Self {
         self.config.max_message_size = Some(max);
         self
     }
 
-    /// Set the maximum frame size (defaults to 16 megabytes)
+    /// Set the maximum frame size (defaults to 16 megabytes).
+    ///
+    /// Like [`WebSocketUpgrade::max_message_size`], but per-frame rather than per reassembled
+    /// message; also surfaces as [`WebSocketError::Capacity`] when exceeded.
     pub fn max_frame_size(mut self, max: usize) -> Self {
         self.config.max_frame_size = Some(max);
         self
     }
 
+    /// Alias for [`WebSocketUpgrade::max_send_queue`]; both set the same field.
+    ///
+    /// Despite the name, `size` counts queued *messages*, not bytes: the `WebSocketConfig` this
+    /// crate builds against only has a message-count `max_send_queue`, not the byte-based
+    /// `write_buffer_size`/`max_write_buffer_size` pair that newer `tungstenite` releases split
+    /// it into. Passing a byte count here would silently be treated as a message count instead.
+    pub fn write_buffer_size(mut self, size: usize) -> Self {
+        self.config.max_send_queue = Some(size);
+        self
+    }
+
+    /// Allow accepting unmasked frames from clients.
+    ///
+    /// Per [RFC 6455 section 5.1], clients must mask every frame they send, and a conforming
+    /// server should close the connection upon receiving an unmasked frame. Setting this to
+    /// `true` tolerates nonconformant clients by accepting unmasked frames anyway. Defaults to
+    /// `false`.
+    ///
+    /// [RFC 6455 section 5.1]: https://www.rfc-editor.org/rfc/rfc6455#section-5.1
+    pub fn accept_unmasked_frames(mut self, accept: bool) -> Self {
+        self.config.accept_unmasked_frames = accept;
+        self
+    }
+
     /// Set the known protocols.
     ///
     /// If the protocol name specified by `Sec-WebSocket-Protocol` header
@@ -225,52 +278,123 @@ impl WebSocketUpgrade {
         self
     }
 
+    /// Add a header to include in the handshake response (`101 Switching Protocols`, or
+    /// `200 OK` for an HTTP/2 Extended CONNECT request).
+    ///
+    /// This can be used to attach e.g. a session id or a custom `X-` header that the client
+    /// reads on connect.
+    ///
+    /// Headers that axum computes itself (`Connection`, `Upgrade`, `Sec-WebSocket-Accept`, and
+    /// `Sec-WebSocket-Protocol`) are ignored, since overriding them would break the handshake.
+    pub fn header(mut self, key: HeaderName, value: HeaderValue) -> Self {
+        if !RESERVED_RESPONSE_HEADERS.contains(&key) {
+            self.headers.insert(key, value);
+        }
+        self
+    }
+
+    /// Add several headers to include in the handshake response.
+    ///
+    /// See [`WebSocketUpgrade::header`] for details.
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        strip_reserved_headers(&mut self.headers);
+        self
+    }
+
+    /// Set how often to ping the client to check that the connection is still alive.
+    ///
+    /// Combine with [`WebSocketUpgrade::keepalive_timeout`] to automatically close connections
+    /// that stop responding, which is useful for detecting dead peers behind NATs or proxies
+    /// without writing any liveness-detection code in the handler.
+    ///
+    /// Has no effect unless [`WebSocketUpgrade::keepalive_timeout`] is also set: without a
+    /// timeout no pings are sent.
+    pub fn keepalive_interval(mut self, interval: Duration) -> Self {
+        self.keepalive_interval = Some(interval);
+        self
+    }
+
+    /// Set how long to wait without hearing from the client before closing the connection.
+    ///
+    /// Has no effect unless [`WebSocketUpgrade::keepalive_interval`] is also set: both must be
+    /// configured for the keepalive heartbeat to be enabled.
+    pub fn keepalive_timeout(mut self, timeout: Duration) -> Self {
+        self.keepalive_timeout = Some(timeout);
+        self
+    }
+
     /// Finalize upgrading the connection and call the provided callback with
     /// the stream.
     ///
     /// When using `WebSocketUpgrade`, the response produced by this method
     /// should be returned from the handler. See the [module docs](self) for an
     /// example.
+    ///
+    /// This uses the default [`TungsteniteBackend`]. To use a different [`WebSocketBackend`],
+    /// see [`WebSocketUpgrade::on_upgrade_with_backend`].
     pub fn on_upgrade<F, Fut>(self, callback: F) -> Response
     where
         F: FnOnce(WebSocket) -> Fut + Send + 'static,
         Fut: Future<Output = ()> + Send + 'static,
     {
-        let on_upgrade = self.on_upgrade;
+        self.on_upgrade_with_backend(callback)
+    }
+
+    /// Like [`WebSocketUpgrade::on_upgrade`], but generic over the [`WebSocketBackend`] used to
+    /// frame messages once the connection has been upgraded.
+    pub fn on_upgrade_with_backend<B, F, Fut>(self, callback: F) -> Response
+    where
+        B: WebSocketBackend,
+        F: FnOnce(WebSocket<B>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
         let config = self.config;
 
         let protocol = self.protocol.clone();
 
-        tokio::spawn(async move {
-            let upgraded = on_upgrade.await.expect("connection upgrade failed");
-            let socket =
-                WebSocketStream::from_raw_socket(upgraded, protocol::Role::Server, Some(config))
-                    .await;
+        let keepalive = match (self.keepalive_interval, self.keepalive_timeout) {
+            (Some(interval), Some(timeout)) => Some((interval, timeout)),
+            _ => None,
+        };
+
+        spawn_on_upgrade(self.on_upgrade, move |upgraded| async move {
+            let backend = B::connect(upgraded, config).await;
+            let keepalive = keepalive.map(|(interval, timeout)| Keepalive {
+                interval: tokio::time::interval(interval),
+                timeout,
+                last_activity: TokioInstant::now(),
+            });
             let socket = WebSocket {
-                inner: socket,
+                inner: backend,
                 protocol,
+                keepalive,
             };
             callback(socket).await;
         });
 
-        #[allow(clippy::declare_interior_mutable_const)]
-        const UPGRADE: HeaderValue = HeaderValue::from_static("upgrade");
         #[allow(clippy::declare_interior_mutable_const)]
         const WEBSOCKET: HeaderValue = HeaderValue::from_static("websocket");
 
-        let mut builder = Response::builder()
-            .status(StatusCode::SWITCHING_PROTOCOLS)
-            .header(header::CONNECTION, UPGRADE)
-            .header(header::UPGRADE, WEBSOCKET)
-            .header(
+        let mut builder = if self.http2 {
+            // RFC 8441: the h2 stream is already established by the Extended CONNECT request,
+            // so we just confirm it with a plain `200 OK` instead of `101 Switching Protocols`.
+            Response::builder().status(StatusCode::OK)
+        } else {
+            connection_upgrade_response(WEBSOCKET).header(
                 header::SEC_WEBSOCKET_ACCEPT,
                 sign(self.sec_websocket_key.as_bytes()),
-            );
+            )
+        };
 
         if let Some(protocol) = self.protocol {
             builder = builder.header(header::SEC_WEBSOCKET_PROTOCOL, protocol);
         }
 
+        if let Some(headers) = builder.headers_mut() {
+            headers.extend(self.headers);
+        }
+
         builder.body(body::boxed(body::Empty::new())).unwrap()
     }
 }
@@ -283,6 +407,41 @@ where
     type Rejection = WebSocketUpgradeRejection;
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        // HTTP/2 has no `Upgrade`/`Connection` handshake; instead the client sends an Extended
+        // CONNECT request (RFC 8441) with a `:protocol` pseudo-header of `websocket`. The server
+        // must have advertised `SETTINGS_ENABLE_CONNECT_PROTOCOL` for hyper to surface this.
+        if parts.version == Version::HTTP_2 && parts.method == Method::CONNECT {
+            let is_websocket = parts
+                .extensions
+                .get::<hyper::ext::Protocol>()
+                .map_or(false, |protocol| protocol.as_str() == "websocket");
+
+            if !is_websocket {
+                return Err(ExtendedConnectNotEnabled.into());
+            }
+
+            let on_upgrade = parts
+                .extensions
+                .remove::<OnUpgrade>()
+                .ok_or(ExtendedConnectNotEnabled)?;
+
+            let sec_websocket_protocol = parts.headers.get(header::SEC_WEBSOCKET_PROTOCOL).cloned();
+
+            return Ok(Self {
+                config: Default::default(),
+                protocol: None,
+                // h2 Extended CONNECT has no `Sec-WebSocket-Key`; `on_upgrade_with_backend` skips
+                // signing it and responds `200 OK` instead of `101` when `http2` is set.
+                sec_websocket_key: HeaderValue::from_static(""),
+                on_upgrade,
+                sec_websocket_protocol,
+                headers: HeaderMap::new(),
+                keepalive_interval: None,
+                keepalive_timeout: None,
+                http2: true,
+            });
+        }
+
         if parts.method != Method::GET {
             return Err(MethodNotGet.into());
         }
@@ -317,10 +476,50 @@ where
             sec_websocket_key,
             on_upgrade,
             sec_websocket_protocol,
+            headers: HeaderMap::new(),
+            keepalive_interval: None,
+            keepalive_timeout: None,
+            http2: false,
         })
     }
 }
 
+/// Remove every header in [`RESERVED_RESPONSE_HEADERS`] from `headers`.
+fn strip_reserved_headers(headers: &mut HeaderMap) {
+    for name in &RESERVED_RESPONSE_HEADERS {
+        headers.remove(name);
+    }
+}
+
+/// Await `on_upgrade` and hand the raw upgraded connection to `callback` in a spawned task.
+///
+/// Shared low-level plumbing for both [`ProtocolUpgrade::on_upgrade`] and
+/// [`WebSocketUpgrade::on_upgrade_with_backend`], so the two don't drift out of sync.
+fn spawn_on_upgrade<F, Fut>(on_upgrade: OnUpgrade, callback: F)
+where
+    F: FnOnce(Upgraded) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(async move {
+        let upgraded = on_upgrade.await.expect("connection upgrade failed");
+        callback(upgraded).await;
+    });
+}
+
+/// Start a `101 Switching Protocols` response for an HTTP/1.1 `Upgrade:` handshake.
+///
+/// Shared by [`ProtocolUpgrade::on_upgrade`] and [`WebSocketUpgrade::on_upgrade_with_backend`]'s
+/// non-HTTP/2 path.
+fn connection_upgrade_response(protocol: HeaderValue) -> ResponseBuilder {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const UPGRADE: HeaderValue = HeaderValue::from_static("upgrade");
+
+    Response::builder()
+        .status(StatusCode::SWITCHING_PROTOCOLS)
+        .header(header::CONNECTION, UPGRADE)
+        .header(header::UPGRADE, protocol)
+}
+
 fn header_eq(headers: &HeaderMap, key: HeaderName, value: &'static str) -> bool {
     if let Some(header) = headers.get(&key) {
         header.as_bytes().eq_ignore_ascii_case(value.as_bytes())
@@ -343,32 +542,237 @@ fn header_contains(headers: &HeaderMap, key: HeaderName, value: &'static str) ->
     }
 }
 
-/// A stream of WebSocket messages.
+/// Extractor that negotiates an arbitrary `Upgrade:` token and hands the handler the raw
+/// upgraded byte stream.
+///
+/// This is the same low-level HTTP/1.1 upgrade mechanism [`WebSocketUpgrade`] is built on top
+/// of, generalized so other protocols (custom line protocols, tunnels, `CONNECT`-style proxies)
+/// can reuse axum's header validation and rejection ergonomics without reimplementing them.
+///
+/// # Example
+///
+/// ```
+/// use axum::{
+///     extract::ws::ProtocolUpgrade,
+///     response::Response,
+///     routing::get,
+///     Router,
+/// };
+///
+/// async fn handler(upgrade: ProtocolUpgrade) -> Response {
+///     upgrade.on_upgrade(|mut stream| async move {
+///         // `stream` is a raw `hyper::upgrade::Upgraded`; read/write bytes on it directly.
+///     })
+/// }
+///
+/// let app = Router::new().route("/tunnel", get(handler));
+/// # async {
+/// # axum::Server::bind(&"".parse().unwrap()).serve(app.into_make_service()).await.unwrap();
+/// # };
+/// ```
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "ws")))]
+pub struct ProtocolUpgrade {
+    on_upgrade: OnUpgrade,
+    protocol: HeaderValue,
+}
+
+impl ProtocolUpgrade {
+    /// Returns the negotiated `Upgrade:` token, e.g. `"websocket"`.
+    pub fn protocol(&self) -> &HeaderValue {
+        &self.protocol
+    }
+
+    /// Respond `101 Switching Protocols`, echoing the negotiated protocol, and call `callback`
+    /// with the raw upgraded byte stream.
+    pub fn on_upgrade<F, Fut>(self, callback: F) -> Response
+    where
+        F: FnOnce(Upgraded) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        spawn_on_upgrade(self.on_upgrade, callback);
+
+        connection_upgrade_response(self.protocol)
+            .body(body::boxed(body::Empty::new()))
+            .unwrap()
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ProtocolUpgrade
+where
+    S: Send + Sync,
+{
+    type Rejection = ProtocolUpgradeRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if !header_contains(&parts.headers, header::CONNECTION, "upgrade") {
+            return Err(InvalidConnectionHeader.into());
+        }
+
+        let protocol = parts
+            .headers
+            .get(header::UPGRADE)
+            .cloned()
+            .ok_or(MissingUpgradeHeader)?;
+
+        let on_upgrade = parts
+            .extensions
+            .remove::<OnUpgrade>()
+            .ok_or(ConnectionNotUpgradable)?;
+
+        Ok(Self {
+            on_upgrade,
+            protocol,
+        })
+    }
+}
+
+/// A trait for pluggable WebSocket wire backends.
+///
+/// axum frames messages with [`tungstenite`] by default (see [`TungsteniteBackend`]), but
+/// [`WebSocket`] is generic over this trait so an alternative implementation (e.g. `ratchet` or
+/// `fastwebsockets`) can be used instead without forking the extractor. axum itself still
+/// performs the handshake header validation (`Upgrade`/`Connection`/`Sec-WebSocket-Key`/
+/// `Sec-WebSocket-Version`); a backend is only responsible for framing [`Message`]s once the
+/// connection has already been upgraded.
+///
+/// # Limitations
+///
+/// This trait frames at the [`Message`] level and exposes no raw frame access, so some
+/// commonly requested features can't be built on top of it without extending the trait itself:
+///
+/// - **`permessage-deflate` (RFC 7692)**: compressing a frame means setting its RSV1 bit, which
+///   isn't observable or controllable through [`Message`]. axum does not negotiate or apply this
+///   extension, even though earlier drafts of this API advertised a `.compression()` builder for
+///   it.
+/// - **Outgoing fragmentation**: splitting one large message into several continuation frames is
+///   a framing-layer decision this trait has no hook for. [`WebSocketUpgrade::max_frame_size`]
+///   still caps how large a single *incoming* frame may be, but axum has no way to fragment an
+///   outgoing [`Message`] for you; a `.fragment_size()` builder was tried and dropped for the
+///   same reason as `.compression()` above.
+/// - **Disabling automatic pong replies**: [`TungsteniteBackend`] answers every `Ping` with a
+///   `Pong` before the message ever reaches this trait's `Stream` impl, so there's no hook here
+///   to suppress it. An `.auto_pong()` builder was tried and dropped in the same way.
+pub trait WebSocketBackend:
+    Stream<Item = Result<Message, WebSocketError>>
+    + Sink<Message, Error = WebSocketError>
+    + Send
+    + Unpin
+    + 'static
+{
+    /// The future returned by [`WebSocketBackend::connect`].
+    type ConnectFuture: Future<Output = Self> + Send;
+
+    /// Take ownership of an upgraded connection and start framing WebSocket messages over it.
+    fn connect(upgraded: Upgraded, config: WebSocketConfig) -> Self::ConnectFuture;
+}
+
+/// The default [`WebSocketBackend`], built on top of [`tokio_tungstenite`].
 #[derive(Debug)]
-pub struct WebSocket {
-    inner: WebSocketStream<Upgraded>,
+pub struct TungsteniteBackend(WebSocketStream<Upgraded>);
+
+impl WebSocketBackend for TungsteniteBackend {
+    type ConnectFuture = Pin<Box<dyn Future<Output = Self> + Send>>;
+
+    fn connect(upgraded: Upgraded, config: WebSocketConfig) -> Self::ConnectFuture {
+        Box::pin(async move {
+            Self(WebSocketStream::from_raw_socket(upgraded, protocol::Role::Server, Some(config)).await)
+        })
+    }
+}
+
+impl Stream for TungsteniteBackend {
+    type Item = Result<Message, WebSocketError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match futures_util::ready!(self.0.poll_next_unpin(cx)) {
+                Some(Ok(msg)) => {
+                    if let Some(msg) = Message::from_tungstenite(msg) {
+                        return Poll::Ready(Some(Ok(msg)));
+                    }
+                }
+                Some(Err(err)) => {
+                    return Poll::Ready(Some(Err(WebSocketError::from_tungstenite(err))))
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl Sink<Message> for TungsteniteBackend {
+    type Error = WebSocketError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.0)
+            .poll_ready(cx)
+            .map_err(WebSocketError::from_tungstenite)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: Message) -> Result<(), Self::Error> {
+        Pin::new(&mut self.0)
+            .start_send(item.into_tungstenite())
+            .map_err(WebSocketError::from_tungstenite)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.0)
+            .poll_flush(cx)
+            .map_err(WebSocketError::from_tungstenite)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.0)
+            .poll_close(cx)
+            .map_err(WebSocketError::from_tungstenite)
+    }
+}
+
+/// A stream of WebSocket messages.
+///
+/// Generic over the [`WebSocketBackend`] used to frame messages; defaults to
+/// [`TungsteniteBackend`], which is what [`WebSocketUpgrade::on_upgrade`] hands to the callback.
+pub struct WebSocket<B = TungsteniteBackend> {
+    inner: B,
     protocol: Option<HeaderValue>,
+    keepalive: Option<Keepalive>,
 }
 
-impl WebSocket {
+impl<B> fmt::Debug for WebSocket<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebSocket")
+            .field("protocol", &self.protocol)
+            .finish()
+    }
+}
+
+/// State for the opt-in ping/pong-timeout heartbeat, configured via
+/// [`WebSocketUpgrade::keepalive_interval`] and [`WebSocketUpgrade::keepalive_timeout`].
+#[derive(Debug)]
+struct Keepalive {
+    interval: Interval,
+    timeout: Duration,
+    last_activity: TokioInstant,
+}
+
+impl<B: WebSocketBackend> WebSocket<B> {
     /// Receive another message.
     ///
     /// Returns `None` if the stream has closed.
-    pub async fn recv(&mut self) -> Option<Result<Message, Error>> {
+    pub async fn recv(&mut self) -> Option<Result<Message, WebSocketError>> {
         self.next().await
     }
 
     /// Send a message.
-    pub async fn send(&mut self, msg: Message) -> Result<(), Error> {
-        self.inner
-            .send(msg.into_tungstenite())
-            .await
-            .map_err(Error::new)
+    pub async fn send(&mut self, msg: Message) -> Result<(), WebSocketError> {
+        self.inner.send(msg).await
     }
 
     /// Gracefully close this WebSocket.
-    pub async fn close(mut self) -> Result<(), Error> {
-        self.inner.close(None).await.map_err(Error::new)
+    pub async fn close(mut self) -> Result<(), WebSocketError> {
+        self.inner.close().await
     }
 
     /// Return the selected WebSocket subprotocol, if one has been chosen.
@@ -377,43 +781,128 @@ impl WebSocket {
     }
 }
 
-impl Stream for WebSocket {
-    type Item = Result<Message, Error>;
+impl<B: WebSocketBackend> Stream for WebSocket<B> {
+    type Item = Result<Message, WebSocketError>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         loop {
-            match futures_util::ready!(self.inner.poll_next_unpin(cx)) {
+            if let Some(keepalive) = &mut self.keepalive {
+                while keepalive.interval.poll_tick(cx).is_ready() {
+                    if keepalive.last_activity.elapsed() > keepalive.timeout {
+                        if Pin::new(&mut self.inner).poll_ready(cx).is_ready() {
+                            let close = Message::Close(Some(CloseFrame {
+                                code: close_code::AWAY,
+                                reason: Cow::Borrowed("keepalive timeout"),
+                            }));
+                            let _ = Pin::new(&mut self.inner).start_send(close);
+                            let _ = Pin::new(&mut self.inner).poll_flush(cx);
+                        }
+                        return Poll::Ready(None);
+                    }
+
+                    // Best-effort: if the socket isn't ready for another frame right now we
+                    // simply skip this tick and try again on the next one.
+                    if Pin::new(&mut self.inner).poll_ready(cx).is_ready() {
+                        let _ = Pin::new(&mut self.inner).start_send(Message::Ping(Vec::new()));
+                        let _ = Pin::new(&mut self.inner).poll_flush(cx);
+                    }
+                }
+            }
+
+            match futures_util::ready!(Pin::new(&mut self.inner).poll_next(cx)) {
                 Some(Ok(msg)) => {
-                    if let Some(msg) = Message::from_tungstenite(msg) {
-                        return Poll::Ready(Some(Ok(msg)));
+                    if let Some(keepalive) = &mut self.keepalive {
+                        keepalive.last_activity = TokioInstant::now();
                     }
+                    return Poll::Ready(Some(Ok(msg)));
                 }
-                Some(Err(err)) => return Poll::Ready(Some(Err(Error::new(err)))),
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
                 None => return Poll::Ready(None),
             }
         }
     }
 }
 
-impl Sink<Message> for WebSocket {
-    type Error = Error;
+impl<B: WebSocketBackend> Sink<Message> for WebSocket<B> {
+    type Error = WebSocketError;
 
     fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        Pin::new(&mut self.inner).poll_ready(cx).map_err(Error::new)
+        Pin::new(&mut self.inner).poll_ready(cx)
     }
 
     fn start_send(mut self: Pin<&mut Self>, item: Message) -> Result<(), Self::Error> {
-        Pin::new(&mut self.inner)
-            .start_send(item.into_tungstenite())
-            .map_err(Error::new)
+        Pin::new(&mut self.inner).start_send(item)
     }
 
     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        Pin::new(&mut self.inner).poll_flush(cx).map_err(Error::new)
+        Pin::new(&mut self.inner).poll_flush(cx)
     }
 
     fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        Pin::new(&mut self.inner).poll_close(cx).map_err(Error::new)
+        Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
+
+/// Errors that can occur when sending or receiving messages on a [`WebSocket`].
+///
+/// These mirror the variants of the underlying `tungstenite::Error` so callers can, for example,
+/// match on [`WebSocketError::ConnectionClosed`] to exit cleanly instead of treating every
+/// failure the same way.
+///
+/// Note that a peer-initiated close handshake is not reported through this type: it arrives as
+/// `Ok(`[`Message::Close`]`(..))` from the [`WebSocket`] stream, same as any other message.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WebSocketError {
+    /// The connection is already closed and will not be reopened.
+    ConnectionClosed,
+    /// Trying to work with an already closed connection.
+    AlreadyClosed,
+    /// An I/O error occurred.
+    Io(std::io::Error),
+    /// A protocol violation occurred.
+    Protocol(String),
+    /// A message or frame exceeded a configured size limit.
+    Capacity(String),
+    /// A text message contained data that was not valid UTF-8.
+    Utf8,
+}
+
+impl WebSocketError {
+    fn from_tungstenite(err: ts::Error) -> Self {
+        match err {
+            ts::Error::ConnectionClosed => Self::ConnectionClosed,
+            ts::Error::AlreadyClosed => Self::AlreadyClosed,
+            ts::Error::Io(err) => Self::Io(err),
+            ts::Error::Capacity(err) => Self::Capacity(err.to_string()),
+            ts::Error::Protocol(err) => Self::Protocol(err.to_string()),
+            ts::Error::Utf8 => Self::Utf8,
+            err => Self::Protocol(err.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for WebSocketError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ConnectionClosed => f.write_str("connection closed normally"),
+            Self::AlreadyClosed => {
+                f.write_str("trying to work with closed connection")
+            }
+            Self::Io(err) => write!(f, "IO error: {err}"),
+            Self::Protocol(err) => write!(f, "WebSocket protocol error: {err}"),
+            Self::Capacity(err) => write!(f, "space limit exceeded: {err}"),
+            Self::Utf8 => f.write_str("invalid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for WebSocketError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
     }
 }
 
@@ -454,10 +943,16 @@ pub struct CloseFrame<'t> {
 // THE SOFTWARE.
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum Message {
-    /// A text WebSocket message
-    Text(String),
-    /// A binary WebSocket message
-    Binary(Vec<u8>),
+    /// A text WebSocket message.
+    ///
+    /// Backed by a reference-counted, UTF-8 validated buffer, so cloning a message (e.g. to fan
+    /// it out to many sockets) does not copy the payload.
+    Text(Utf8Bytes),
+    /// A binary WebSocket message.
+    ///
+    /// Backed by a reference-counted buffer, so cloning a message (e.g. to fan it out to many
+    /// sockets) does not copy the payload.
+    Binary(Bytes),
     /// A ping message with the specified payload
     ///
     /// The payload here must have a length less than 125 bytes.
@@ -480,8 +975,8 @@ pub enum Message {
 impl Message {
     fn into_tungstenite(self) -> ts::Message {
         match self {
-            Self::Text(text) => ts::Message::Text(text),
-            Self::Binary(binary) => ts::Message::Binary(binary),
+            Self::Text(text) => ts::Message::Text(text.into_string()),
+            Self::Binary(binary) => ts::Message::Binary(binary.into()),
             Self::Ping(ping) => ts::Message::Ping(ping),
             Self::Pong(pong) => ts::Message::Pong(pong),
             Self::Close(Some(close)) => ts::Message::Close(Some(ts::protocol::CloseFrame {
@@ -494,8 +989,10 @@ impl Message {
 
     fn from_tungstenite(message: ts::Message) -> Option<Self> {
         match message {
-            ts::Message::Text(text) => Some(Self::Text(text)),
-            ts::Message::Binary(binary) => Some(Self::Binary(binary)),
+            // `tungstenite::Message::Text` is already validated UTF-8, so this conversion never
+            // allocates beyond what `String` already owns.
+            ts::Message::Text(text) => Some(Self::Text(Utf8Bytes::from(text))),
+            ts::Message::Binary(binary) => Some(Self::Binary(binary.into())),
             ts::Message::Ping(ping) => Some(Self::Ping(ping)),
             ts::Message::Pong(pong) => Some(Self::Pong(pong)),
             ts::Message::Close(Some(close)) => Some(Self::Close(Some(CloseFrame {
@@ -510,22 +1007,26 @@ impl Message {
     }
 
     /// Consume the WebSocket and return it as binary data.
-    pub fn into_data(self) -> Vec<u8> {
+    pub fn into_data(self) -> Bytes {
         match self {
             Self::Text(string) => string.into_bytes(),
-            Self::Binary(data) | Self::Ping(data) | Self::Pong(data) => data,
-            Self::Close(None) => Vec::new(),
-            Self::Close(Some(frame)) => frame.reason.into_owned().into_bytes(),
+            Self::Binary(data) => data,
+            Self::Ping(data) | Self::Pong(data) => Bytes::from(data),
+            Self::Close(None) => Bytes::new(),
+            Self::Close(Some(frame)) => Bytes::from(frame.reason.into_owned().into_bytes()),
         }
     }
 
     /// Attempt to consume the WebSocket message and convert it to a String.
-    pub fn into_text(self) -> Result<String, Error> {
+    pub fn into_text(self) -> Result<String, WebSocketError> {
         match self {
-            Self::Text(string) => Ok(string),
-            Self::Binary(data) | Self::Ping(data) | Self::Pong(data) => Ok(String::from_utf8(data)
-                .map_err(|err| err.utf8_error())
-                .map_err(Error::new)?),
+            Self::Text(text) => Ok(text.into_string()),
+            Self::Binary(data) => {
+                String::from_utf8(data.into()).map_err(|_| WebSocketError::Utf8)
+            }
+            Self::Ping(data) | Self::Pong(data) => {
+                String::from_utf8(data).map_err(|_| WebSocketError::Utf8)
+            }
             Self::Close(None) => Ok(String::new()),
             Self::Close(Some(frame)) => Ok(frame.reason.into_owned()),
         }
@@ -533,11 +1034,14 @@ impl Message {
 
     /// Attempt to get a &str from the WebSocket message,
     /// this will try to convert binary data to utf8.
-    pub fn to_text(&self) -> Result<&str, Error> {
+    pub fn to_text(&self) -> Result<&str, WebSocketError> {
         match *self {
-            Self::Text(ref string) => Ok(string),
-            Self::Binary(ref data) | Self::Ping(ref data) | Self::Pong(ref data) => {
-                Ok(std::str::from_utf8(data).map_err(Error::new)?)
+            Self::Text(ref text) => Ok(text.as_str()),
+            Self::Binary(ref data) => {
+                std::str::from_utf8(data).map_err(|_| WebSocketError::Utf8)
+            }
+            Self::Ping(ref data) | Self::Pong(ref data) => {
+                std::str::from_utf8(data).map_err(|_| WebSocketError::Utf8)
             }
             Self::Close(None) => Ok(""),
             Self::Close(Some(ref frame)) => Ok(&frame.reason),
@@ -545,12 +1049,95 @@ impl Message {
     }
 }
 
-impl From<Message> for Vec<u8> {
+impl From<Message> for Bytes {
     fn from(msg: Message) -> Self {
         msg.into_data()
     }
 }
 
+impl From<String> for Message {
+    fn from(text: String) -> Self {
+        Self::Text(text.into())
+    }
+}
+
+impl From<&str> for Message {
+    fn from(text: &str) -> Self {
+        Self::Text(text.into())
+    }
+}
+
+impl From<Vec<u8>> for Message {
+    fn from(data: Vec<u8>) -> Self {
+        Self::Binary(data.into())
+    }
+}
+
+/// A UTF-8 validated, reference-counted buffer used for [`Message::Text`].
+///
+/// `Utf8Bytes` derefs to `str` and is cheap to clone: cloning shares the underlying buffer
+/// instead of copying it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Utf8Bytes(Bytes);
+
+impl Utf8Bytes {
+    /// Creates a new `Utf8Bytes` from a static string, without copying.
+    pub fn from_static(text: &'static str) -> Self {
+        Self(Bytes::from_static(text.as_bytes()))
+    }
+
+    /// Returns the string slice backed by this buffer.
+    pub fn as_str(&self) -> &str {
+        // SAFETY: all constructors of `Utf8Bytes` validate (or already guarantee) that the
+        // underlying bytes are valid UTF-8.
+        unsafe { std::str::from_utf8_unchecked(&self.0) }
+    }
+
+    fn into_bytes(self) -> Bytes {
+        self.0
+    }
+
+    fn into_string(self) -> String {
+        // SAFETY: see `as_str`.
+        unsafe { String::from_utf8_unchecked(self.0.into()) }
+    }
+}
+
+impl std::ops::Deref for Utf8Bytes {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for Utf8Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<String> for Utf8Bytes {
+    fn from(text: String) -> Self {
+        Self(Bytes::from(text))
+    }
+}
+
+impl From<&str> for Utf8Bytes {
+    fn from(text: &str) -> Self {
+        Self(Bytes::copy_from_slice(text.as_bytes()))
+    }
+}
+
+impl TryFrom<Bytes> for Utf8Bytes {
+    type Error = WebSocketError;
+
+    fn try_from(bytes: Bytes) -> Result<Self, Self::Error> {
+        std::str::from_utf8(&bytes).map_err(|_| WebSocketError::Utf8)?;
+        Ok(Self(bytes))
+    }
+}
+
 fn sign(key: &[u8]) -> HeaderValue {
     let mut sha1 = Sha1::default();
     sha1.update(key);
@@ -611,6 +1198,36 @@ pub mod rejection {
         pub struct ConnectionNotUpgradable;
     }
 
+    define_rejection! {
+        #[status = NOT_IMPLEMENTED]
+        #[body = "HTTP/2 WebSockets require the server to enable Extended CONNECT"]
+        /// Rejection type for [`WebSocketUpgrade`](super::WebSocketUpgrade).
+        ///
+        /// Returned for an HTTP/2 `CONNECT` request whose `:protocol` pseudo-header isn't
+        /// `websocket`, which happens when the server hasn't advertised
+        /// `SETTINGS_ENABLE_CONNECT_PROTOCOL` (RFC 8441).
+        pub struct ExtendedConnectNotEnabled;
+    }
+
+    define_rejection! {
+        #[status = BAD_REQUEST]
+        #[body = "`Upgrade` header missing"]
+        /// Rejection type for [`ProtocolUpgrade`](super::ProtocolUpgrade).
+        pub struct MissingUpgradeHeader;
+    }
+
+    composite_rejection! {
+        /// Rejection used for [`ProtocolUpgrade`](super::ProtocolUpgrade).
+        ///
+        /// Contains one variant for each way the [`ProtocolUpgrade`](super::ProtocolUpgrade)
+        /// extractor can fail.
+        pub enum ProtocolUpgradeRejection {
+            InvalidConnectionHeader,
+            MissingUpgradeHeader,
+            ConnectionNotUpgradable,
+        }
+    }
+
     composite_rejection! {
         /// Rejection used for [`WebSocketUpgrade`](super::WebSocketUpgrade).
         ///
@@ -623,6 +1240,7 @@ pub mod rejection {
             InvalidWebSocketVersionHeader,
             WebSocketKeyHeaderMissing,
             ConnectionNotUpgradable,
+            ExtendedConnectNotEnabled,
         }
     }
 }
@@ -721,4 +1339,269 @@ mod tests {
 
         assert_eq!(res.status(), StatusCode::OK);
     }
+
+    #[test]
+    fn utf8_bytes_try_from_validates_utf8() {
+        let valid = Bytes::from_static("héllo".as_bytes());
+        assert_eq!(Utf8Bytes::try_from(valid).unwrap().as_str(), "héllo");
+
+        let invalid = Bytes::from_static(&[0xff, 0xfe, 0xfd]);
+        assert!(matches!(
+            Utf8Bytes::try_from(invalid),
+            Err(WebSocketError::Utf8)
+        ));
+    }
+
+    #[test]
+    fn message_into_data_round_trips_every_variant() {
+        assert_eq!(Message::from("hi").into_data(), Bytes::from_static(b"hi"));
+        assert_eq!(
+            Message::from(vec![1u8, 2, 3]).into_data(),
+            Bytes::from_static(&[1, 2, 3])
+        );
+        assert_eq!(
+            Message::Ping(vec![4, 5]).into_data(),
+            Bytes::from_static(&[4, 5])
+        );
+        assert_eq!(
+            Message::Pong(vec![6, 7]).into_data(),
+            Bytes::from_static(&[6, 7])
+        );
+        assert_eq!(Message::Close(None).into_data(), Bytes::new());
+        assert_eq!(
+            Message::Close(Some(CloseFrame {
+                code: close_code::NORMAL,
+                reason: Cow::Borrowed("bye"),
+            }))
+            .into_data(),
+            Bytes::from_static(b"bye")
+        );
+    }
+
+    #[test]
+    fn message_into_text_and_to_text_round_trip_every_variant() {
+        for msg in [
+            Message::from("hi"),
+            Message::from(b"hi".to_vec()),
+            Message::Ping(b"hi".to_vec()),
+            Message::Pong(b"hi".to_vec()),
+            Message::Close(Some(CloseFrame {
+                code: close_code::NORMAL,
+                reason: Cow::Borrowed("hi"),
+            })),
+        ] {
+            assert_eq!(msg.to_text().unwrap(), "hi");
+            assert_eq!(msg.into_text().unwrap(), "hi");
+        }
+
+        assert_eq!(Message::Close(None).to_text().unwrap(), "");
+        assert_eq!(Message::Close(None).into_text().unwrap(), "");
+
+        let invalid_utf8 = Message::Binary(Bytes::from_static(&[0xff, 0xfe]));
+        assert!(matches!(invalid_utf8.to_text(), Err(WebSocketError::Utf8)));
+        assert!(matches!(invalid_utf8.into_text(), Err(WebSocketError::Utf8)));
+    }
+
+    #[test]
+    fn from_tungstenite_maps_known_variants() {
+        assert!(matches!(
+            WebSocketError::from_tungstenite(ts::Error::ConnectionClosed),
+            WebSocketError::ConnectionClosed
+        ));
+        assert!(matches!(
+            WebSocketError::from_tungstenite(ts::Error::AlreadyClosed),
+            WebSocketError::AlreadyClosed
+        ));
+        assert!(matches!(
+            WebSocketError::from_tungstenite(ts::Error::Utf8),
+            WebSocketError::Utf8
+        ));
+        assert!(matches!(
+            WebSocketError::from_tungstenite(ts::Error::Io(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "boom"
+            ))),
+            WebSocketError::Io(_)
+        ));
+        assert!(matches!(
+            WebSocketError::from_tungstenite(ts::Error::Capacity(
+                ts::error::CapacityError::MessageTooLong {
+                    size: 1,
+                    max_size: 0,
+                }
+            )),
+            WebSocketError::Capacity(_)
+        ));
+        // Anything tungstenite adds that we don't explicitly match (e.g. a handshake-level
+        // failure like `AttackAttempt`) must still land on the catch-all `Protocol` arm instead
+        // of panicking.
+        assert!(matches!(
+            WebSocketError::from_tungstenite(ts::Error::AttackAttempt),
+            WebSocketError::Protocol(_)
+        ));
+    }
+
+    #[test]
+    fn strip_reserved_headers_removes_every_reserved_name() {
+        let mut headers = HeaderMap::new();
+        for name in &RESERVED_RESPONSE_HEADERS {
+            headers.insert(name.clone(), HeaderValue::from_static("attacker-controlled"));
+        }
+        headers.insert(
+            HeaderName::from_static("x-custom"),
+            HeaderValue::from_static("1"),
+        );
+
+        strip_reserved_headers(&mut headers);
+
+        for name in &RESERVED_RESPONSE_HEADERS {
+            assert!(!headers.contains_key(name));
+        }
+        assert_eq!(headers.get("x-custom").unwrap(), "1");
+    }
+
+    #[tokio::test]
+    async fn http2_extended_connect_requires_websocket_protocol() {
+        let mut req = Request::builder()
+            .version(Version::HTTP_2)
+            .method(Method::CONNECT)
+            .body(Body::empty())
+            .unwrap();
+        req.extensions_mut()
+            .insert(hyper::ext::Protocol::from_static("not-websocket"));
+        let (mut parts, _body) = req.into_parts();
+
+        let rejection = WebSocketUpgrade::from_request_parts(&mut parts, &())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            rejection,
+            WebSocketUpgradeRejection::ExtendedConnectNotEnabled(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn http2_extended_connect_upgrades_with_200_and_no_switching_protocols_headers() {
+        // Outside a real hyper connection there's no pending upgrade for this request to claim,
+        // but `hyper::upgrade::on` still hands back a usable `OnUpgrade` value (one that simply
+        // errors if ever awaited), which is all `from_request_parts` needs to see in order to
+        // take the Extended CONNECT branch.
+        let on_upgrade = hyper::upgrade::on(Request::new(Body::empty()));
+
+        let mut req = Request::builder()
+            .version(Version::HTTP_2)
+            .method(Method::CONNECT)
+            .body(Body::empty())
+            .unwrap();
+        req.extensions_mut()
+            .insert(hyper::ext::Protocol::from_static("websocket"));
+        req.extensions_mut().insert(on_upgrade);
+        let (mut parts, _body) = req.into_parts();
+
+        let upgrade = WebSocketUpgrade::from_request_parts(&mut parts, &())
+            .await
+            .unwrap();
+
+        let response = upgrade.on_upgrade(|_socket| async {});
+
+        // RFC 8441: the h2 stream is already established by the CONNECT request, so the
+        // handshake confirms with a plain `200 OK` rather than `101 Switching Protocols`, and
+        // carries none of the HTTP/1.1 upgrade headers.
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key(header::CONNECTION));
+        assert!(!response.headers().contains_key(header::UPGRADE));
+        assert!(!response.headers().contains_key(header::SEC_WEBSOCKET_ACCEPT));
+    }
+
+    /// A [`WebSocketBackend`] whose readiness and delivered messages are controlled by hand, so
+    /// keepalive behavior can be tested without a real socket.
+    struct MockBackend {
+        send_ready: bool,
+        sent: Vec<Message>,
+    }
+
+    impl WebSocketBackend for MockBackend {
+        type ConnectFuture = std::future::Ready<Self>;
+
+        fn connect(_upgraded: Upgraded, _config: WebSocketConfig) -> Self::ConnectFuture {
+            unreachable!("tests construct MockBackend directly, never via connect")
+        }
+    }
+
+    impl Stream for MockBackend {
+        type Item = Result<Message, WebSocketError>;
+
+        fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Poll::Pending
+        }
+    }
+
+    impl Sink<Message> for MockBackend {
+        type Error = WebSocketError;
+
+        fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            if self.send_ready {
+                Poll::Ready(Ok(()))
+            } else {
+                Poll::Pending
+            }
+        }
+
+        fn start_send(mut self: Pin<&mut Self>, item: Message) -> Result<(), Self::Error> {
+            self.sent.push(item);
+            Ok(())
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn timed_out_keepalive() -> Keepalive {
+        Keepalive {
+            interval: tokio::time::interval(Duration::from_millis(1)),
+            timeout: Duration::from_secs(1),
+            // Already older than `timeout`, so the very first tick fires the close path.
+            last_activity: TokioInstant::now().checked_sub(Duration::from_secs(60)).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn keepalive_timeout_does_not_start_send_before_poll_ready() {
+        let mut socket = WebSocket {
+            inner: MockBackend {
+                send_ready: false,
+                sent: Vec::new(),
+            },
+            protocol: None,
+            keepalive: Some(timed_out_keepalive()),
+        };
+
+        assert!(socket.recv().await.is_none());
+        // `poll_ready` never returned `Ready`, so the close frame must not have been sent.
+        assert!(socket.inner.sent.is_empty());
+    }
+
+    #[tokio::test]
+    async fn keepalive_timeout_sends_close_once_ready() {
+        let mut socket = WebSocket {
+            inner: MockBackend {
+                send_ready: true,
+                sent: Vec::new(),
+            },
+            protocol: None,
+            keepalive: Some(timed_out_keepalive()),
+        };
+
+        assert!(socket.recv().await.is_none());
+        assert!(matches!(
+            socket.inner.sent.as_slice(),
+            [Message::Close(Some(_))]
+        ));
+    }
 }